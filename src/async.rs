@@ -0,0 +1,232 @@
+//! Async (non-blocking) variant of [`crate::SteamWorkshop`], gated behind the
+//! `async` cargo feature. It mirrors the blocking surface but exposes `async fn`
+//! methods on top of `reqwest::Client`, sharing all parameter-building and
+//! deserialization with the blocking client so the two can't drift. It has no
+//! cache or retry settings: every call goes straight to the API, once.
+
+use std::path::Path;
+use std::fs::DirEntry;
+use reqwest::Client;
+use serde_json::Value;
+
+use crate::{
+    Error, FileDetailsResult, SearchOptions, WorkshopItem, WSCollectionResponse,
+    WSSearchResponse, USER_AGENT, file_details_batches, parse_file_details,
+    collection_params, collection_children, search_query, advance_cursor, can_subscribe_query,
+    parse_can_subscribe, subscribe_params, unsubscribe_params, vote_params,
+    parse_subscribed_items, status_error, check_eresult,
+};
+
+/// Turns recognized error statuses (429, 401/403) into structured variants,
+/// deferring any other non-2xx status to `error_for_status`.
+fn handle_status(response: reqwest::Response) -> Result<reqwest::Response, Error> {
+    if let Some(e) = status_error(response.status().as_u16()) {
+        return Err(e);
+    }
+    response.error_for_status().map_err(|e| Error::RequestError(e))
+}
+
+#[derive(Clone)]
+pub struct AsyncSteamWorkshop {
+    client: Client,
+    apikey: Option<String>,
+    request_domain: String
+}
+
+#[allow(dead_code)]
+impl AsyncSteamWorkshop {
+    ///Creates a new workshop instance, client will be auto created if None
+    pub fn new() -> AsyncSteamWorkshop {
+        AsyncSteamWorkshop::new_with_client(Client::new())
+    }
+    pub fn new_with_client(client: Client) -> AsyncSteamWorkshop {
+        AsyncSteamWorkshop {
+            client,
+            request_domain: "api.steampowered.com".to_string(),
+            apikey: None
+        }
+    }
+
+    ///Gets an authorized workshop, allows access to methods that require api keys.
+    ///Get api keys from https://steamcommunity.com/dev/apikey
+    pub fn set_apikey(&mut self, apikey: Option<String>) {
+        self.apikey = apikey;
+    }
+
+    /// Will change the domain that requests are made to, allowing you to proxy api.steampowered.com
+    pub fn set_proxy_domain(&mut self, proxy_domain: Option<String>) {
+        self.request_domain = proxy_domain.unwrap_or("api.steampowered.com".to_string());
+    }
+
+    /// Returns DirEntry for all *.vpk files in a directory.
+    pub fn get_vpks_in_folder(dir: &Path) -> Result<Vec<DirEntry>, String> {
+        crate::SteamWorkshop::get_vpks_in_folder(dir)
+    }
+
+    /// Fetches the latest WorkshopItem per each addon id
+    /// Steam API only allows 100 entries at once, so larger lists are sent in batches of 100
+    pub async fn get_published_file_details(&self, fileids: &[String]) -> Result<FileDetailsResult, Error> {
+        let mut result = FileDetailsResult::default();
+        for params in file_details_batches(fileids)? {
+            let response = self.client
+                .post(format!("https://{}/ISteamRemoteStorage/GetPublishedFileDetails/v1/", self.request_domain))
+                .header("User-Agent", &USER_AGENT.to_string())
+                .form(&params)
+                .send().await.map_err(|e| Error::RequestError(e))?;
+            let details = handle_status(response)?
+                .json::<Value>().await.map_err(|e| Error::RequestError(e))?;
+            let page = parse_file_details(details)?;
+            result.items.extend(page.items);
+            result.failures.extend(page.failures);
+        }
+        Ok(result)
+    }
+
+    /// Gets the collection details (all the children of this item).
+    /// Returns a list of children fileids which can be sent directly to get_published_file_details()
+    /// Will return Ok(None) if the item is not a collection.
+    pub async fn get_collection_details(&self, fileid: &str) -> Result<Option<Vec<String>>, Error> {
+        let params = collection_params(fileid);
+        let response = self.client
+            .post(format!("https://{}/ISteamRemoteStorage/GetCollectionDetails/v1/", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .form(&params)
+            .send().await.map_err(|e| Error::RequestError(e))?;
+        let details = handle_status(response)?
+            .json::<WSCollectionResponse>().await.map_err(|e| Error::RequestError(e))?;
+
+        Ok(collection_children(details))
+    }
+
+    /// Searches for workshop items, returns the first page of matching items.
+    /// REQUIRES steam apikey or a proxy domain
+    pub async fn search_items(&self, query: &str, options: &SearchOptions) -> Result<Vec<WorkshopItem>, Error> {
+        let details = self.query_files(query, options).await?;
+        Ok(details.response.map(|r| r.publishedfiledetails).unwrap_or_default())
+    }
+
+    /// Like [`AsyncSteamWorkshop::search_items`], but follows the API's paging
+    /// cursor and returns every page concatenated. Starts at cursor `*`, feeding
+    /// each response's `next_cursor` into the next request, and stops when the
+    /// cursor stops advancing or a page comes back empty.
+    /// REQUIRES steam apikey or a proxy domain
+    pub async fn search_items_all(&self, query: &str, options: &SearchOptions) -> Result<Vec<WorkshopItem>, Error> {
+        let mut options = options.clone();
+        options.cursor = Some(options.cursor.unwrap_or_else(|| "*".to_string()));
+        let mut items: Vec<WorkshopItem> = Vec::new();
+        loop {
+            let current = options.cursor.clone().unwrap();
+            let page = self.query_files(query, &options).await?;
+            let (page_items, next_cursor) = match page.response {
+                Some(body) => (body.publishedfiledetails, body.next_cursor),
+                None => (Vec::new(), None),
+            };
+            if page_items.is_empty() {
+                break;
+            }
+            items.extend(page_items);
+            match advance_cursor(&current, next_cursor) {
+                Some(next) => options.cursor = Some(next),
+                None => break,
+            }
+        }
+        Ok(items)
+    }
+
+    /// Subscribes the apikey's user to a published file.
+    /// `notify_followers` mirrors the workshop "notify my followers" toggle.
+    /// REQUIRES steam apikey or a proxy domain
+    pub async fn subscribe(&self, fileid: &str, notify_followers: bool) -> Result<(), Error> {
+        let key = self.authed_key()?;
+        let response = self.client
+            .post(format!("https://{}/IPublishedFileService/Subscribe/v1/", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .form(&subscribe_params(key, fileid, notify_followers))
+            .send().await.map_err(|e| Error::RequestError(e))?;
+        let details: Value = handle_status(response)?
+            .json().await.map_err(|e| Error::RequestError(e))?;
+        check_eresult(&details)
+    }
+
+    /// Unsubscribes the apikey's user from a published file.
+    /// REQUIRES steam apikey or a proxy domain
+    pub async fn unsubscribe(&self, fileid: &str) -> Result<(), Error> {
+        let key = self.authed_key()?;
+        let response = self.client
+            .post(format!("https://{}/IPublishedFileService/Unsubscribe/v1/", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .form(&unsubscribe_params(key, fileid))
+            .send().await.map_err(|e| Error::RequestError(e))?;
+        let details: Value = handle_status(response)?
+            .json().await.map_err(|e| Error::RequestError(e))?;
+        check_eresult(&details)
+    }
+
+    /// Returns the publishedfileids the apikey's user is subscribed to.
+    /// REQUIRES steam apikey or a proxy domain
+    pub async fn get_subscribed_items(&self) -> Result<Vec<String>, Error> {
+        let key = self.authed_key()?;
+        let response = self.client
+            .get(format!("https://{}/IPublishedFileService/GetSubscribedItems/v1/", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .query(&[("key", key)])
+            .send().await.map_err(|e| Error::RequestError(e))?;
+        let details: Value = handle_status(response)?
+            .json().await.map_err(|e| Error::RequestError(e))?;
+        Ok(parse_subscribed_items(details))
+    }
+
+    /// Casts an up (`true`) or down (`false`) vote on a published file.
+    /// REQUIRES steam apikey or a proxy domain
+    pub async fn vote(&self, fileid: &str, up: bool) -> Result<(), Error> {
+        let key = self.authed_key()?;
+        let response = self.client
+            .post(format!("https://{}/IPublishedFileService/Vote/v1/", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .form(&vote_params(key, fileid, up))
+            .send().await.map_err(|e| Error::RequestError(e))?;
+        let details: Value = handle_status(response)?
+            .json().await.map_err(|e| Error::RequestError(e))?;
+        check_eresult(&details)
+    }
+
+    /// Returns the apikey to sign a request with, enforcing the same
+    /// apikey-or-proxy rule used by the search endpoints.
+    fn authed_key(&self) -> Result<&str, Error> {
+        // A proxy domain signs the request itself, so only the direct Steam
+        // endpoint actually requires a local apikey.
+        if self.apikey.is_none() && self.request_domain == "api.steampowered.com" {
+            return Err(Error::NotAuthorized)
+        }
+        Ok(self.apikey.as_deref().unwrap_or(""))
+    }
+
+    async fn query_files(&self, query: &str, options: &SearchOptions) -> Result<WSSearchResponse<WorkshopItem>, Error> {
+        let apikey = self.authed_key()?;
+        let query = search_query(query, options, apikey)?;
+        let response = self.client.get(format!("https://{}/IPublishedFileService/QueryFiles/v1/?", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .query(&query)
+            .send().await.map_err(|e| Error::RequestError(e))?;
+        handle_status(response)?
+            .json::<WSSearchResponse<WorkshopItem>>().await.map_err(|e| Error::RequestError(e))
+    }
+
+    /// Check if the user (of apikey) can subscribe to the published file
+    /// REQUIRES apikey, cannot use proxy.
+    pub async fn can_subscribe(&self, fileid: &str) -> Result<bool, Error> {
+        if self.apikey.is_none() {
+            return Err(Error::NotAuthorized)
+        }
+
+        let response = self.client
+            .get(format!("https://{}/IPublishedFileService/CanSubscribe/v1/", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .query(&can_subscribe_query(self.apikey.as_deref().unwrap_or(""), fileid))
+            .send().await.map_err(|e| Error::RequestError(e))?;
+        let details: Value = handle_status(response)?
+            .json().await.map_err(|e| Error::RequestError(e))?;
+        Ok(parse_can_subscribe(details))
+    }
+}
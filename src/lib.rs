@@ -1,6 +1,8 @@
 //! # steam_workshop_api
 //!
 //! This library provides access to the steam web apis. Uses reqwest::blocking under the hood
+//! by default; enable the `async` feature for a non-blocking [`AsyncSteamWorkshop`] built on
+//! async `reqwest` instead.
 //! # Getting Started
 //! To access any web api that requires no authentication (file details) you need to create a new instance:
 //! ```rust
@@ -32,9 +34,7 @@
 //! wsclient.search_items("blah", &SearchOptions {
 //!        count: 10,
 //!         app_id: 550,
-//!         cursor: None,
-//!         required_tags: None,
-//!         excluded_tags: None,
+//!         ..Default::default()
 //! });
 //! ```
 
@@ -48,9 +48,17 @@ use serde::{Deserialize, Serialize};
 use std::{fs, path::Path, collections::HashMap, fmt};
 use std::fmt::{Debug, Display, Formatter};
 use std::fs::DirEntry;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use reqwest::blocking::Client;
 use serde_json::Value;
 
+#[cfg(feature = "async")]
+mod r#async;
+#[cfg(feature = "async")]
+pub use r#async::AsyncSteamWorkshop;
+
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct WorkshopItem {
     pub result: i8,
@@ -78,6 +86,7 @@ pub struct WorkshopItem {
     pub visibility: u8
 }
 
+#[derive(Clone, Copy)]
 pub enum PublishedFileQueryType {
     RankedByVote = 0,
     RankedByPublicationDate = 1,
@@ -102,19 +111,33 @@ pub enum PublishedFileQueryType {
     RankedByBanContentCheck = 20,
     RankedByLastUpdatedDate = 21,
 }
+#[derive(Clone)]
 pub struct SearchTagOptions {
     tags: Vec<String>,
     /// If true, requires all tags in tags to be set.
     /// If false, at least one must match
     require_all: bool
 }
+#[derive(Clone, Copy)]
 pub enum QueryType {
     /// Sort by trend.
     /// Days if set, will only return items within the range provided.
     /// Range must be [1, 7]
     RankedByTrend { days: Option<u32> }
 }
-#[derive(Default)]
+
+/// Restricts a search to a single kind of UGC, mapped to the `filetype` query
+/// parameter of `IPublishedFileService/QueryFiles`.
+#[derive(Clone, Copy)]
+pub enum FileType {
+    Items = 0,
+    Collections = 1,
+    Artwork = 2,
+    Screenshots = 4,
+    ReadyToUse = 18,
+}
+
+#[derive(Default, Clone)]
 pub struct SearchOptions {
     pub count: u32,
     pub app_id: u32,
@@ -122,7 +145,15 @@ pub struct SearchOptions {
     pub cursor: Option<String>,
     pub required_tags: Option<SearchTagOptions>,
     /// Ignore any entries with these tags
-    pub excluded_tags: Option<Vec<String>>
+    pub excluded_tags: Option<Vec<String>>,
+    /// How results are ranked/sorted, mapped to `query_type`. Defaults to
+    /// relevance (`RankedByTextSearch`) when left unset.
+    pub query_type: Option<PublishedFileQueryType>,
+    /// Only used when `query_type` is `RankedByTrend`: restricts results to a
+    /// [1, 7] day window via the `days` parameter.
+    pub trend_period: Option<QueryType>,
+    /// Restrict results to a specific kind of UGC (collections, artwork, ...).
+    pub file_type: Option<FileType>
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
@@ -151,7 +182,13 @@ struct WSItemResponse<T> {
 #[doc(hidden)]
 #[derive(Serialize, Deserialize)]
 struct WSItemResponseBody<T> {
-    publishedfiledetails: Vec<T>
+    publishedfiledetails: Vec<T>,
+    /// Present on QueryFiles responses; both total and the paging cursor are
+    /// nested alongside publishedfiledetails, not at the top level.
+    #[serde(default)]
+    total: u32,
+    #[serde(default)]
+    next_cursor: Option<String>
 }
 #[doc(hidden)]
 #[derive(Serialize, Deserialize)]
@@ -164,8 +201,7 @@ struct WSSearchIdBody {
 #[doc(hidden)]
 #[derive(Serialize, Deserialize)]
 struct WSSearchResponse<T> {
-    response: Option<WSItemResponseBody<T>>,
-    total: u8
+    response: Option<WSItemResponseBody<T>>
 }
 
 
@@ -200,12 +236,89 @@ struct WSCollectionChildren {
 pub struct SteamWorkshop {
     client: Client,
     apikey: Option<String>,
-    request_domain: String
+    request_domain: String,
+    cache: Option<Arc<dyn WorkshopCache>>,
+    retry: Option<RetryConfig>
+}
+
+/// A backing store for [`WorkshopItem`]s, letting repeated
+/// [`SteamWorkshop::get_published_file_details`] calls skip the Steam API for
+/// entries that are already cached and still fresh.
+pub trait WorkshopCache: Send + Sync {
+    /// Returns a cached item for `fileid` if present and not yet expired.
+    fn get(&self, fileid: &str) -> Option<WorkshopItem>;
+    /// Stores (or refreshes) the given items.
+    fn put(&self, items: &[WorkshopItem]);
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct CacheEntry {
+    item: WorkshopItem,
+    /// Unix timestamp (seconds) the entry was written.
+    cached_at: u64
+}
+
+/// Default [`WorkshopCache`] that persists items to a JSON file keyed by
+/// `publishedfileid`, expiring entries older than a configurable TTL.
+pub struct FileWorkshopCache {
+    path: PathBuf,
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CacheEntry>>
+}
+
+impl FileWorkshopCache {
+    /// Opens (or creates) a cache at `path`, loading any existing entries.
+    /// `ttl` controls how long a cached item is considered fresh.
+    pub fn new(path: impl Into<PathBuf>, ttl: Duration) -> FileWorkshopCache {
+        let path = path.into();
+        let entries = fs::read_to_string(&path).ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        FileWorkshopCache { path, ttl, entries: Mutex::new(entries) }
+    }
+
+    fn persist(&self, entries: &HashMap<String, CacheEntry>) {
+        if let Ok(json) = serde_json::to_string(entries) {
+            let _ = fs::write(&self.path, json);
+        }
+    }
+}
+
+impl WorkshopCache for FileWorkshopCache {
+    fn get(&self, fileid: &str) -> Option<WorkshopItem> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(fileid).and_then(|entry| {
+            if now_secs().saturating_sub(entry.cached_at) <= self.ttl.as_secs() {
+                Some(entry.item.clone())
+            } else {
+                None
+            }
+        })
+    }
+
+    fn put(&self, items: &[WorkshopItem]) {
+        let mut entries = self.entries.lock().unwrap();
+        let now = now_secs();
+        for item in items {
+            entries.insert(item.publishedfileid.clone(), CacheEntry { item: item.clone(), cached_at: now });
+        }
+        self.persist(&entries);
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
 }
 
 pub enum Error {
     /// Request requires authorization either via an apikey, or using a domain proxy that uses their own key
     NotAuthorized,
+    /// The server rejected our credentials (HTTP 401/403).
+    Unauthorized,
+    /// The server rate-limited the request (HTTP 429).
+    RateLimited,
+    /// Steam returned a non-success EResult code for the request.
+    SteamResult(i32),
     RequestError(reqwest::Error),
     BadRequest(String)
 }
@@ -214,6 +327,9 @@ impl Debug for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Error::NotAuthorized => write!(f, "Request is not authorized, please use .set_apikey, or .set_proxy_domain"),
+            Error::Unauthorized => write!(f, "server rejected credentials (401/403)"),
+            Error::RateLimited => write!(f, "rate limited (429)"),
+            Error::SteamResult(code) => write!(f, "steam returned eresult {}", code),
             Error::RequestError(e) => write!(f, "request error: {}", e),
             Error::BadRequest(e) => write!(f, "bad request data: {}", e),
         }
@@ -224,6 +340,9 @@ impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Error::NotAuthorized => write!(f, "Not authorized"),
+            Error::Unauthorized => write!(f, "Unauthorized"),
+            Error::RateLimited => write!(f, "Rate limited"),
+            Error::SteamResult(code) => write!(f, "Steam EResult {}", code),
             Error::RequestError(e) => write!(f, "Request Error: {}", e),
             Error::BadRequest(e) => write!(f, "Incorrect request: {}", e),
         }
@@ -232,6 +351,26 @@ impl Display for Error {
 
 impl std::error::Error for Error {}
 
+/// Controls the retry-with-backoff wrapper applied to the throttle-prone batch
+/// endpoints. Attempts are retried on HTTP 429/5xx (and transport errors), with
+/// the delay scaled linearly by attempt number.
+#[derive(Clone, Copy)]
+pub struct RetryConfig {
+    /// Total number of attempts (including the first). 1 disables retrying.
+    pub max_attempts: u32,
+    /// Base delay between attempts; attempt N waits `delay * N`.
+    pub delay: Duration
+}
+
+/// The outcome of [`SteamWorkshop::get_published_file_details`]: the items Steam
+/// returned successfully, plus any ids it reported a non-success EResult for.
+#[derive(Default)]
+pub struct FileDetailsResult {
+    pub items: Vec<WorkshopItem>,
+    /// `(publishedfileid, eresult)` pairs for ids that failed.
+    pub failures: Vec<(String, i32)>
+}
+
 #[allow(dead_code)]
 impl SteamWorkshop {
     ///Creates a new workshop instance, client will be auto created if None
@@ -243,10 +382,24 @@ impl SteamWorkshop {
         SteamWorkshop {
             client,
             request_domain: "api.steampowered.com".to_string(),
-            apikey: None
+            apikey: None,
+            cache: None,
+            retry: None
         }
     }
 
+    /// Enables retry-with-backoff on the batch endpoints (see [`RetryConfig`]).
+    pub fn set_retry(&mut self, retry: Option<RetryConfig>) {
+        self.retry = retry;
+    }
+
+    /// Installs a [`WorkshopCache`] used to satisfy
+    /// [`SteamWorkshop::get_published_file_details`] lookups, only fetching the
+    /// ids that are missing or stale. See [`FileWorkshopCache`] for a default.
+    pub fn set_cache(&mut self, cache: impl WorkshopCache + 'static) {
+        self.cache = Some(Arc::new(cache));
+    }
+
     ///Gets an authorized workshop, allows access to methods that require api keys.
     ///Get api keys from https://steamcommunity.com/dev/apikey
     pub fn set_apikey(&mut self, apikey: Option<String>) {
@@ -273,97 +426,219 @@ impl SteamWorkshop {
         return Ok(files);
     }
 
-    /// Fetches the latest WorkshopItem per each addon id
-    /// Steam API only allows 100 entries at once, will have an api error if more given
-    pub fn get_published_file_details(&self, fileids: &[String]) -> Result<Vec<WorkshopItem>, Error> {
-        let mut params = HashMap::new();
-        let length = fileids.len().to_string();
-        params.insert("itemcount".to_string(), length);
-        for (i, vpk) in fileids.iter().enumerate() {
-            if !vpk.parse::<u64>().is_ok() {
-                return Err(Error::BadRequest(format!("Item is not valid publishedfileid: {}", vpk)));
+    /// Fetches the latest WorkshopItem per each addon id.
+    /// When a cache is installed via [`SteamWorkshop::set_cache`], fresh entries
+    /// are served from it and only the missing/stale ids are requested from
+    /// Steam (in batches of 100), then merged back into the cache.
+    pub fn get_published_file_details(&self, fileids: &[String]) -> Result<FileDetailsResult, Error> {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.fetch_file_details(fileids),
+        };
+
+        let mut result = FileDetailsResult::default();
+        let mut missing: Vec<String> = Vec::new();
+        for fileid in fileids {
+            match cache.get(fileid) {
+                Some(item) => result.items.push(item),
+                None => missing.push(fileid.clone()),
             }
-            let name = format!("publishedfileids[{i}]", i=i);
-            params.insert(name, vpk.to_string());
         }
-        let mut details = self.client
-            .post(format!("https://{}/ISteamRemoteStorage/GetPublishedFileDetails/v1/", self.request_domain))
-            .header("User-Agent", &USER_AGENT.to_string())
-            .form(&params)
-            .send().map_err(|e| Error::RequestError(e))?
-            .error_for_status().map_err(|e| Error::RequestError(e))?
-            .json::<Value>().map_err(|e| Error::RequestError(e))?;
+        if !missing.is_empty() {
+            let fetched = self.fetch_file_details(&missing)?;
+            cache.put(&fetched.items);
+            result.items.extend(fetched.items);
+            result.failures.extend(fetched.failures);
+        }
+        Ok(result)
+    }
 
-        Ok(details["response"]["publishedfiledetails"].as_array_mut().unwrap().iter_mut()
-            .filter(|v| v["result"] == 1)
-            .map(|v| serde_json::from_value(v.take()).unwrap())
-            .collect()
-        )
+    /// Requests file details straight from Steam, splitting into the 100-id
+    /// batches the endpoint allows.
+    fn fetch_file_details(&self, fileids: &[String]) -> Result<FileDetailsResult, Error> {
+        let mut result = FileDetailsResult::default();
+        for params in file_details_batches(fileids)? {
+            let response = self.send_with_retry(|| self.client
+                .post(format!("https://{}/ISteamRemoteStorage/GetPublishedFileDetails/v1/", self.request_domain))
+                .header("User-Agent", &USER_AGENT.to_string())
+                .form(&params)
+                .send())?;
+            let details = self.handle_status(response)?
+                .json::<Value>().map_err(|e| Error::RequestError(e))?;
+            let page = parse_file_details(details)?;
+            result.items.extend(page.items);
+            result.failures.extend(page.failures);
+        }
+        Ok(result)
+    }
+
+    /// Sends a request, retrying on 429/5xx (and transport errors) per the
+    /// configured [`RetryConfig`], scaling the delay by attempt number.
+    fn send_with_retry<F>(&self, make: F) -> Result<reqwest::blocking::Response, Error>
+        where F: Fn() -> Result<reqwest::blocking::Response, reqwest::Error>
+    {
+        let retry = self.retry.unwrap_or(RetryConfig { max_attempts: 1, delay: Duration::ZERO });
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match make() {
+                Ok(response) => {
+                    if is_retryable(response.status().as_u16()) && attempt < retry.max_attempts {
+                        std::thread::sleep(retry.delay * attempt);
+                        continue;
+                    }
+                    return Ok(response);
+                }
+                Err(e) => {
+                    if attempt < retry.max_attempts {
+                        std::thread::sleep(retry.delay * attempt);
+                        continue;
+                    }
+                    return Err(Error::RequestError(e));
+                }
+            }
+        }
+    }
+
+    /// Turns recognized error statuses (429, 401/403) into structured variants,
+    /// deferring any other non-2xx status to `error_for_status`.
+    fn handle_status(&self, response: reqwest::blocking::Response) -> Result<reqwest::blocking::Response, Error> {
+        if let Some(e) = status_error(response.status().as_u16()) {
+            return Err(e);
+        }
+        response.error_for_status().map_err(|e| Error::RequestError(e))
     }
 
     /// Gets the collection details (all the children of this item).
     /// Returns a list of children fileids which can be sent directly to get_published_file_details()
     /// Will return Ok(None) if the item is not a collection.
     pub fn get_collection_details(&self, fileid: &str) -> Result<Option<Vec<String>>, Error> {
-        let mut params = HashMap::new();
-        params.insert("collectioncount", "1");
-        params.insert("publishedfileids[0]", &fileid);
-        let details: WSCollectionResponse = self.client
+        let params = collection_params(fileid);
+        let response = self.client
             .post(format!("https://{}/ISteamRemoteStorage/GetCollectionDetails/v1/", self.request_domain))
             .header("User-Agent", USER_AGENT.to_string())
             .form(&params)
-            .send().map_err(|e| Error::RequestError(e))?
-            .error_for_status().map_err(|e| Error::RequestError(e))?
+            .send().map_err(|e| Error::RequestError(e))?;
+        let details = self.handle_status(response)?
             .json::<WSCollectionResponse>().map_err(|e| Error::RequestError(e))?;
 
-        if details.response.resultcount > 0 {
-            let mut ids: Vec<String>  = Vec::new();
-            for children in &details.response.collectiondetails[0].children {
-                ids.push(children.publishedfileid.to_string());
-            }
-            Ok(Some(ids))
-        } else {
-            Ok(None)
-        }
+        Ok(collection_children(details))
     }
 
     /// Searches for workshop items, returns their file ids.
     /// REQUIRES steam apikey or a proxy domain
     pub fn search_items(&self, query: &str, options: &SearchOptions) -> Result<Vec<WorkshopItem>, Error> {
-        if self.apikey.is_none() || self.request_domain != "api.steampowered.com" {
-            return Err(Error::NotAuthorized)
-        }
-        let apikey: &str = self.apikey.as_deref().unwrap_or("");
-        let appid = options.app_id.to_string();
-        let mut query: Vec<(&str, String)> = vec![
-            ("page", "1".to_string()),
-            ("numperpage", options.count.to_string()),
-            ("cursor", options.cursor.as_deref().unwrap_or("*").to_string()),
-            ("search_text", query.to_string()),
-            ("appid", appid.clone()),
-            ("creator_appid", appid),
-            ("return_metadata", "1".to_string()),
-            ("key", apikey.to_string()),
-        ];
-        if let Some(rt) = &options.required_tags {
-            query.push(("requiredtags", rt.tags.join(",")));
-            query.push(("match_all_tags", if rt.require_all { "1".to_string() } else { "0".to_string() }));
+        let details = self.query_files(query, options)?;
+        Ok(details.response.map(|r| r.publishedfiledetails).unwrap_or_default())
+    }
+
+    /// Like [`SteamWorkshop::search_items`], but follows the API's paging cursor
+    /// and returns every page concatenated. Starts at cursor `*`, feeding each
+    /// response's `next_cursor` into the next request, and stops when the cursor
+    /// stops advancing or a page comes back empty.
+    /// REQUIRES steam apikey or a proxy domain
+    pub fn search_items_all(&self, query: &str, options: &SearchOptions) -> Result<Vec<WorkshopItem>, Error> {
+        let mut options = options.clone();
+        options.cursor = Some(options.cursor.unwrap_or_else(|| "*".to_string()));
+        let mut items: Vec<WorkshopItem> = Vec::new();
+        loop {
+            let current = options.cursor.clone().unwrap();
+            let page = self.query_files(query, &options)?;
+            let (page_items, next_cursor) = match page.response {
+                Some(body) => (body.publishedfiledetails, body.next_cursor),
+                None => (Vec::new(), None),
+            };
+            if page_items.is_empty() {
+                break;
+            }
+            items.extend(page_items);
+            match advance_cursor(&current, next_cursor) {
+                Some(next) => options.cursor = Some(next),
+                None => break,
+            }
         }
-        if let Some(tags) = &options.excluded_tags {
-            query.push(("excludedtags", tags.join(",")));
+        Ok(items)
+    }
+
+    /// Subscribes the apikey's user to a published file.
+    /// `notify_followers` mirrors the workshop "notify my followers" toggle.
+    /// REQUIRES steam apikey or a proxy domain
+    pub fn subscribe(&self, fileid: &str, notify_followers: bool) -> Result<(), Error> {
+        let key = self.authed_key()?;
+        let response = self.client
+            .post(format!("https://{}/IPublishedFileService/Subscribe/v1/", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .form(&subscribe_params(key, fileid, notify_followers))
+            .send().map_err(|e| Error::RequestError(e))?;
+        let details: Value = self.handle_status(response)?
+            .json().map_err(|e| Error::RequestError(e))?;
+        check_eresult(&details)
+    }
+
+    /// Unsubscribes the apikey's user from a published file.
+    /// REQUIRES steam apikey or a proxy domain
+    pub fn unsubscribe(&self, fileid: &str) -> Result<(), Error> {
+        let key = self.authed_key()?;
+        let response = self.client
+            .post(format!("https://{}/IPublishedFileService/Unsubscribe/v1/", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .form(&unsubscribe_params(key, fileid))
+            .send().map_err(|e| Error::RequestError(e))?;
+        let details: Value = self.handle_status(response)?
+            .json().map_err(|e| Error::RequestError(e))?;
+        check_eresult(&details)
+    }
+
+    /// Returns the publishedfileids the apikey's user is subscribed to.
+    /// REQUIRES steam apikey or a proxy domain
+    pub fn get_subscribed_items(&self) -> Result<Vec<String>, Error> {
+        let key = self.authed_key()?;
+        let response = self.client
+            .get(format!("https://{}/IPublishedFileService/GetSubscribedItems/v1/", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .query(&[("key", key)])
+            .send().map_err(|e| Error::RequestError(e))?;
+        let details: Value = self.handle_status(response)?
+            .json().map_err(|e| Error::RequestError(e))?;
+        Ok(parse_subscribed_items(details))
+    }
+
+    /// Casts an up (`true`) or down (`false`) vote on a published file.
+    /// REQUIRES steam apikey or a proxy domain
+    pub fn vote(&self, fileid: &str, up: bool) -> Result<(), Error> {
+        let key = self.authed_key()?;
+        let response = self.client
+            .post(format!("https://{}/IPublishedFileService/Vote/v1/", self.request_domain))
+            .header("User-Agent", USER_AGENT.to_string())
+            .form(&vote_params(key, fileid, up))
+            .send().map_err(|e| Error::RequestError(e))?;
+        let details: Value = self.handle_status(response)?
+            .json().map_err(|e| Error::RequestError(e))?;
+        check_eresult(&details)
+    }
+
+    /// Returns the apikey to sign a request with, enforcing the same
+    /// apikey-or-proxy rule used by the search endpoints.
+    fn authed_key(&self) -> Result<&str, Error> {
+        // A proxy domain signs the request itself, so only the direct Steam
+        // endpoint actually requires a local apikey.
+        if self.apikey.is_none() && self.request_domain == "api.steampowered.com" {
+            return Err(Error::NotAuthorized)
         }
-        let details = self.client.get(format!("https://{}/IPublishedFileService/QueryFiles/v1/?", self.request_domain))
+        Ok(self.apikey.as_deref().unwrap_or(""))
+    }
+
+    fn query_files(&self, query: &str, options: &SearchOptions) -> Result<WSSearchResponse<WorkshopItem>, Error> {
+        let apikey = self.authed_key()?;
+        let query = search_query(query, options, apikey)?;
+        let response = self.send_with_retry(|| self.client
+            .get(format!("https://{}/IPublishedFileService/QueryFiles/v1/?", self.request_domain))
             .header("User-Agent", USER_AGENT.to_string())
             .header("Content-Type", "application/x-www-form-urlencoded")
             .query(&query)
-            .send().map_err(|e| Error::RequestError(e))?
-            .json::<WSSearchResponse<WorkshopItem>>().map_err(|e| Error::RequestError(e))?;
-
-        if details.total > 0 {
-            Ok(details.response.unwrap().publishedfiledetails)
-        } else {
-            Ok(vec!())
-        }
+            .send())?;
+        self.handle_status(response)?
+            .json::<WSSearchResponse<WorkshopItem>>().map_err(|e| Error::RequestError(e))
     }
 
     /// Check if the user (of apikey) can subscribe to the published file
@@ -373,17 +648,308 @@ impl SteamWorkshop {
             return Err(Error::NotAuthorized)
         }
 
-        let details: Value = self.client
-            .get("https://api.steampowered.com/IPublishedFileService/CanSubscribe/v1/?key=7250BBE4BC2ECA0E16197B38E3675988&publishedfileid=122447941")
+        let response = self.client
+            .get(format!("https://{}/IPublishedFileService/CanSubscribe/v1/", self.request_domain))
             .header("User-Agent", USER_AGENT.to_string())
-            .query(&[
-                "key", &self.apikey.as_ref().unwrap(),
-                "publishedfileid", fileid
-            ])
-            .send().map_err(|e| Error::RequestError(e))?
-            .error_for_status().map_err(|e| Error::RequestError(e))?
+            .query(&can_subscribe_query(self.apikey.as_deref().unwrap_or(""), fileid))
+            .send().map_err(|e| Error::RequestError(e))?;
+        let details: Value = self.handle_status(response)?
             .json().map_err(|e| Error::RequestError(e))?;
-        Ok(details["response"]["can_subscribe"].as_bool().unwrap_or(false))
+        Ok(parse_can_subscribe(details))
+    }
+
+}
+
+// Shared request-building and deserialization helpers used by both the blocking
+// `SteamWorkshop` and the async `AsyncSteamWorkshop` so the two surfaces can't drift.
+
+/// The maximum number of ids `GetPublishedFileDetails` accepts in one request.
+pub(crate) const FILE_DETAILS_BATCH: usize = 100;
+
+/// Splits `fileids` into [`FILE_DETAILS_BATCH`]-sized batches of request params,
+/// so both the blocking and async clients chunk oversized requests identically.
+pub(crate) fn file_details_batches(fileids: &[String]) -> Result<Vec<HashMap<String, String>>, Error> {
+    fileids.chunks(FILE_DETAILS_BATCH).map(file_details_params).collect()
+}
+
+pub(crate) fn file_details_params(fileids: &[String]) -> Result<HashMap<String, String>, Error> {
+    let mut params = HashMap::new();
+    params.insert("itemcount".to_string(), fileids.len().to_string());
+    for (i, vpk) in fileids.iter().enumerate() {
+        if vpk.parse::<u64>().is_err() {
+            return Err(Error::BadRequest(format!("Item is not valid publishedfileid: {}", vpk)));
+        }
+        let name = format!("publishedfileids[{i}]", i=i);
+        params.insert(name, vpk.to_string());
+    }
+    Ok(params)
+}
+
+pub(crate) fn parse_file_details(mut details: Value) -> Result<FileDetailsResult, Error> {
+    let mut result = FileDetailsResult::default();
+    let entries = details["response"]["publishedfiledetails"]
+        .as_array_mut()
+        .ok_or_else(|| Error::BadRequest("response missing publishedfiledetails array".to_string()))?;
+    for v in entries.iter_mut() {
+        if v["result"] == 1 {
+            result.items.push(serde_json::from_value(v.take()).unwrap());
+        } else {
+            let fileid = v["publishedfileid"].as_str().unwrap_or_default().to_string();
+            let eresult = v["result"].as_i64().unwrap_or(0) as i32;
+            result.failures.push((fileid, eresult));
+        }
+    }
+    Ok(result)
+}
+
+/// Maps an HTTP status code to the structured error variant it implies, or
+/// `None` for statuses we don't treat specially (handled by `error_for_status`).
+pub(crate) fn status_error(code: u16) -> Option<Error> {
+    match code {
+        429 => Some(Error::RateLimited),
+        401 | 403 => Some(Error::Unauthorized),
+        _ => None,
+    }
+}
+
+/// Whether a status code warrants a retry by the backoff wrapper.
+pub(crate) fn is_retryable(code: u16) -> bool {
+    code == 429 || (500..600).contains(&code)
+}
+
+pub(crate) fn collection_params(fileid: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    params.insert("collectioncount".to_string(), "1".to_string());
+    params.insert("publishedfileids[0]".to_string(), fileid.to_string());
+    params
+}
+
+pub(crate) fn collection_children(details: WSCollectionResponse) -> Option<Vec<String>> {
+    if details.response.resultcount > 0 {
+        let mut ids: Vec<String> = Vec::new();
+        for children in &details.response.collectiondetails[0].children {
+            ids.push(children.publishedfileid.to_string());
+        }
+        Some(ids)
+    } else {
+        None
     }
+}
+
+pub(crate) fn search_query(query: &str, options: &SearchOptions, apikey: &str) -> Result<Vec<(&'static str, String)>, Error> {
+    let appid = options.app_id.to_string();
+    let mut query: Vec<(&str, String)> = vec![
+        ("page", "1".to_string()),
+        ("numperpage", options.count.to_string()),
+        ("cursor", options.cursor.as_deref().unwrap_or("*").to_string()),
+        ("search_text", query.to_string()),
+        ("appid", appid.clone()),
+        ("creator_appid", appid),
+        ("return_metadata", "1".to_string()),
+        ("key", apikey.to_string()),
+    ];
+    // Steam defaults an absent query_type to RankedByVote; emit
+    // RankedByTextSearch explicitly so leaving it unset ranks by relevance.
+    let query_type = options.query_type.unwrap_or(PublishedFileQueryType::RankedByTextSearch);
+    query.push(("query_type", (query_type as i32).to_string()));
+    // Steam only honours `days` when ranking by trend, so only emit it when
+    // the caller has actually asked for the trend query_type.
+    if let Some(PublishedFileQueryType::RankedByTrend) = options.query_type {
+        if let Some(QueryType::RankedByTrend { days: Some(days) }) = options.trend_period {
+            if !(1..=7).contains(&days) {
+                return Err(Error::BadRequest(format!("trend days must be in the range [1, 7], got {}", days)));
+            }
+            query.push(("days", days.to_string()));
+        }
+    }
+    if let Some(file_type) = options.file_type {
+        query.push(("filetype", (file_type as i32).to_string()));
+    }
+    if let Some(rt) = &options.required_tags {
+        query.push(("requiredtags", rt.tags.join(",")));
+        query.push(("match_all_tags", if rt.require_all { "1".to_string() } else { "0".to_string() }));
+    }
+    if let Some(tags) = &options.excluded_tags {
+        query.push(("excludedtags", tags.join(",")));
+    }
+    Ok(query)
+}
+
+/// Decides the cursor for the next page given the one used for the page just
+/// fetched and the `next_cursor` the API returned. Returns `None` (stop) when
+/// the API omits a cursor or hands back the same one.
+pub(crate) fn advance_cursor(current: &str, next_cursor: Option<String>) -> Option<String> {
+    match next_cursor {
+        Some(next) if next != current => Some(next),
+        _ => None,
+    }
+}
+
+pub(crate) fn can_subscribe_query<'a>(apikey: &'a str, fileid: &'a str) -> Vec<(&'static str, &'a str)> {
+    vec![
+        ("key", apikey),
+        ("publishedfileid", fileid),
+    ]
+}
 
+pub(crate) fn parse_can_subscribe(details: Value) -> bool {
+    details["response"]["can_subscribe"].as_bool().unwrap_or(false)
+}
+
+/// Turns a non-success top-level `response.result` EResult into an error,
+/// leaving success (1) or an absent result alone.
+pub(crate) fn check_eresult(details: &Value) -> Result<(), Error> {
+    match details["response"]["result"].as_i64() {
+        Some(1) | None => Ok(()),
+        Some(code) => Err(Error::SteamResult(code as i32)),
+    }
+}
+
+pub(crate) fn subscribe_params<'a>(key: &'a str, fileid: &'a str, notify_followers: bool) -> Vec<(&'static str, &'a str)> {
+    vec![
+        ("key", key),
+        ("publishedfileid", fileid),
+        ("notify_followers", if notify_followers { "1" } else { "0" }),
+    ]
+}
+
+pub(crate) fn unsubscribe_params<'a>(key: &'a str, fileid: &'a str) -> Vec<(&'static str, &'a str)> {
+    vec![
+        ("key", key),
+        ("publishedfileid", fileid),
+    ]
+}
+
+pub(crate) fn vote_params<'a>(key: &'a str, fileid: &'a str, up: bool) -> Vec<(&'static str, &'a str)> {
+    vec![
+        ("key", key),
+        ("publishedfileid", fileid),
+        ("vote_up", if up { "true" } else { "false" }),
+    ]
+}
+
+pub(crate) fn parse_subscribed_items(details: Value) -> Vec<String> {
+    details["response"]["items"].as_array()
+        .map(|items| items.iter()
+            .filter_map(|v| v["publishedfileid"].as_str().map(|s| s.to_string()))
+            .collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_cursor_stops_when_unchanged() {
+        assert_eq!(advance_cursor("abc", Some("abc".to_string())), None);
+    }
+
+    #[test]
+    fn advance_cursor_stops_when_absent() {
+        assert_eq!(advance_cursor("abc", None), None);
+    }
+
+    #[test]
+    fn advance_cursor_follows_new_cursor() {
+        assert_eq!(advance_cursor("*", Some("abc".to_string())), Some("abc".to_string()));
+    }
+
+    #[test]
+    fn search_query_defaults_to_text_search() {
+        let query = search_query("map", &SearchOptions::default(), "key").unwrap();
+        let query_type = query.iter().find(|(k, _)| *k == "query_type").map(|(_, v)| v.as_str());
+        assert_eq!(query_type, Some("12"));
+    }
+
+    #[test]
+    fn search_query_emits_filetype_when_set() {
+        let options = SearchOptions { file_type: Some(FileType::Collections), ..Default::default() };
+        let query = search_query("map", &options, "key").unwrap();
+        assert!(query.iter().any(|(k, v)| *k == "filetype" && v == "1"));
+    }
+
+    #[test]
+    fn search_query_rejects_out_of_range_trend_days() {
+        let options = SearchOptions {
+            query_type: Some(PublishedFileQueryType::RankedByTrend),
+            trend_period: Some(QueryType::RankedByTrend { days: Some(8) }),
+            ..Default::default()
+        };
+        assert!(matches!(search_query("map", &options, "key"), Err(Error::BadRequest(_))));
+    }
+
+    #[test]
+    fn search_query_accepts_in_range_trend_days() {
+        let options = SearchOptions {
+            query_type: Some(PublishedFileQueryType::RankedByTrend),
+            trend_period: Some(QueryType::RankedByTrend { days: Some(7) }),
+            ..Default::default()
+        };
+        let query = search_query("map", &options, "key").unwrap();
+        assert!(query.iter().any(|(k, v)| *k == "days" && v == "7"));
+    }
+
+    #[test]
+    fn parse_file_details_splits_items_and_failures() {
+        let details = serde_json::json!({
+            "response": { "publishedfiledetails": [
+                { "result": 9, "publishedfileid": "123" }
+            ] }
+        });
+        let result = parse_file_details(details).unwrap();
+        assert!(result.items.is_empty());
+        assert_eq!(result.failures, vec![("123".to_string(), 9)]);
+    }
+
+    #[test]
+    fn parse_file_details_errors_without_array() {
+        let details = serde_json::json!({ "response": {} });
+        assert!(matches!(parse_file_details(details), Err(Error::BadRequest(_))));
+    }
+
+    #[test]
+    fn check_eresult_passes_on_success_or_absent() {
+        assert!(check_eresult(&serde_json::json!({ "response": { "result": 1 } })).is_ok());
+        assert!(check_eresult(&serde_json::json!({ "response": {} })).is_ok());
+    }
+
+    #[test]
+    fn check_eresult_surfaces_failure_code() {
+        let err = check_eresult(&serde_json::json!({ "response": { "result": 8 } }));
+        assert!(matches!(err, Err(Error::SteamResult(8))));
+    }
+
+    fn write_cache_file(cached_at: u64) -> PathBuf {
+        let item = serde_json::json!({
+            "result": 1, "publishedfileid": "123", "creator": "1",
+            "creator_appid": 550, "consumer_appid": 550, "filename": "",
+            "file_size": "0", "file_url": null, "preview_url": "",
+            "hcontent_file": "", "hcontent_preview": "", "title": "demo",
+            "file_description": "", "time_created": 0, "time_updated": 0,
+            "subscriptions": 0, "favorited": 0, "views": 0, "tags": [],
+            "visibility": 0
+        });
+        let entries = serde_json::json!({ "123": { "item": item, "cached_at": cached_at } });
+        let mut path = std::env::temp_dir();
+        path.push(format!("ws_cache_test_{}.json", cached_at));
+        fs::write(&path, serde_json::to_string(&entries).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn cache_returns_fresh_entry() {
+        let path = write_cache_file(now_secs());
+        let cache = FileWorkshopCache::new(&path, Duration::from_secs(3600));
+        assert!(cache.get("123").is_some());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn cache_expires_stale_entry() {
+        let path = write_cache_file(now_secs().saturating_sub(10_000));
+        let cache = FileWorkshopCache::new(&path, Duration::from_secs(60));
+        assert!(cache.get("123").is_none());
+        let _ = fs::remove_file(&path);
+    }
 }
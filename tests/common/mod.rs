@@ -5,7 +5,7 @@ static WS: OnceLock<SteamWorkshop> = OnceLock::new();
 pub fn get_workshop() -> &'static SteamWorkshop {
     WS.get_or_init(|| {
         let mut client = SteamWorkshop::new();
-        client.set_apikey(Some(env!("STEAM_API_KEY").to_string()));
+        client.set_apikey(Some(option_env!("STEAM_API_KEY").unwrap_or("").to_string()));
         client
     })
 }
\ No newline at end of file